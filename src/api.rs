@@ -15,24 +15,99 @@ pub(crate) struct CliArgs {
     pub config_path: String,
 }
 
+/// `#[serde(default)]` fills in any field missing from an on-disk config
+/// with `E2ETestConfig::default()`'s value for that field, so a config
+/// saved before a later field was added keeps loading instead of failing
+/// `load_e2e_config` with a "missing field" error.
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct E2ETestConfig {
     pub greeting_receiver_url: String,
-    pub greeting_api_logg_url: String,
-    pub num_iterations: i8
+    pub greeting_api_url: String,
+    pub greeting_log_limit: u16,
+    pub num_iterations: u16,
+    /// Base delay for the first retry/poll attempt, before jitter is applied.
+    pub verify_base_delay_ms: u64,
+    /// Upper bound a single backoff delay is allowed to grow to.
+    pub verify_delay_cap_ms: u64,
+    /// Total time the log-verification loop may spend waiting for new entries.
+    pub verify_max_elapsed_secs: u64,
+    /// Which `MessageGenerator` backend to build via `build_generator`.
+    pub generator: GeneratorConfig,
+    /// If set, the Prometheus text exposition of the run's metrics is
+    /// written here once the run completes.
+    pub metrics_output_path: Option<String>,
+    /// If true, after tail verification succeeds, replay the whole log from
+    /// the start offset and confirm every sent task's greeting landed and
+    /// in the order the tasks were sent, instead of trusting only the
+    /// tail-poll result.
+    pub full_log_verification: bool,
+    /// Base delay between polls of `/health/live` and `/health/ready` while
+    /// waiting for the greeting services to come up; later delays grow
+    /// exponentially (with jitter) up to 5s.
+    pub readiness_poll_interval_ms: u64,
+    /// Overall time budget for the readiness wait before giving up.
+    pub readiness_timeout_secs: u64,
+    /// Per-request timeout for `GreetingApiClient`.
+    pub api_request_timeout_secs: u64,
+    /// Base delay for `GreetingApiClient`'s read-call retries, before jitter.
+    pub api_retry_base_delay_ms: u64,
+    /// Upper bound a single `GreetingApiClient` retry delay may grow to.
+    pub api_retry_delay_cap_ms: u64,
+    /// Total time `GreetingApiClient`'s retries may spend on one call.
+    pub api_retry_max_elapsed_secs: u64,
+    /// Whether failed sends to `greeting_receiver_url` are retried. Off by
+    /// default: submitting a greeting isn't idempotent, so retrying after a
+    /// response is merely lost in transit risks a duplicate greeting.
+    pub retry_sends: bool,
 }
 
 impl Default for E2ETestConfig {
     fn default() -> Self {
         E2ETestConfig {
             greeting_receiver_url: "http://localhost:80800".to_string(),
-            greeting_api_logg_url: "http://localhost:80800".to_string(),
+            greeting_api_url: "http://localhost:80800".to_string(),
+            greeting_log_limit: 10,
             num_iterations: 0,
+            verify_base_delay_ms: 200,
+            verify_delay_cap_ms: 5_000,
+            verify_max_elapsed_secs: 10,
+            generator: GeneratorConfig::default(),
+            metrics_output_path: None,
+            full_log_verification: false,
+            readiness_poll_interval_ms: 200,
+            readiness_timeout_secs: 30,
+            api_request_timeout_secs: 30,
+            api_retry_base_delay_ms: 200,
+            api_retry_delay_cap_ms: 5_000,
+            api_retry_max_elapsed_secs: 10,
+            retry_sends: false,
         }
     }
 }
 
-pub (crate) fn load_e22_config(path: &str) -> Result<E2ETestConfig, ConfyError> {
+/// Selects the `MessageGenerator` backend used to produce greeting content.
+/// New backends are added as new variants here and a matching arm in
+/// `message_generators::build_generator`, without touching the e2e loop.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(tag = "type")]
+pub enum GeneratorConfig {
+    #[default]
+    Random,
+    Ollama {
+        model: String,
+        host: String,
+        prompt: String,
+    },
+    OpenAiCompatible {
+        base_url: String,
+        api_key_env: String,
+        model: String,
+        prompt: String,
+    },
+}
+
+pub (crate) fn load_e2e_config(path: &str) -> Result<E2ETestConfig, ConfyError> {
     let config_path = Path::new(&path);
     let cfg: E2ETestConfig = confy::load_path(config_path)?;
     info!("Loaded E2E config: {:?}",cfg);
@@ -0,0 +1,151 @@
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+
+/// Exponential backoff with full jitter: `delay = min(base * multiplier^attempt, cap)`,
+/// and the actual sleep is sampled uniformly from `[0, delay]`.
+#[derive(Debug, Clone, Copy)]
+pub struct BackoffPolicy {
+    pub base: Duration,
+    pub multiplier: f64,
+    pub cap: Duration,
+    pub max_elapsed: Duration,
+}
+
+impl BackoffPolicy {
+    pub fn new(base: Duration, multiplier: f64, cap: Duration, max_elapsed: Duration) -> Self {
+        BackoffPolicy {
+            base,
+            multiplier,
+            cap,
+            max_elapsed,
+        }
+    }
+
+    /// Computes the (pre-jitter) delay bound for a zero-based `attempt`.
+    ///
+    /// Clamps in `f64` seconds before building the `Duration`: for a large
+    /// enough `attempt`, `base * multiplier.powi(attempt)` overflows what
+    /// `Duration` can represent, and `Duration::mul_f64` panics on that
+    /// rather than saturating, so the cap has to be applied before the
+    /// conversion instead of after.
+    fn delay_bound(&self, attempt: u32) -> Duration {
+        let exp = self.multiplier.powi(attempt as i32);
+        let bounded_secs = (self.base.as_secs_f64() * exp).min(self.cap.as_secs_f64());
+        Duration::from_secs_f64(bounded_secs)
+    }
+
+    /// Samples the actual sleep duration for `attempt`, uniformly in `[0, delay_bound]`.
+    pub fn next_delay(&self, attempt: u32) -> Duration {
+        let bound_ms = self.delay_bound(attempt).as_millis().max(1) as u64;
+        Duration::from_millis(rand::thread_rng().gen_range(0..=bound_ms))
+    }
+}
+
+/// Tracks retry state for a single operation: attempts taken so far and the
+/// elapsed-time budget, rather than a fixed attempt count. `reset` lets
+/// callers drop back to attempt 0 whenever real progress is made, so a
+/// steady trickle of results isn't penalized by ever-growing delays.
+pub struct BackoffBudget {
+    policy: BackoffPolicy,
+    attempt: u32,
+    started_at: Instant,
+}
+
+impl BackoffBudget {
+    pub fn new(policy: BackoffPolicy) -> Self {
+        BackoffBudget {
+            policy,
+            attempt: 0,
+            started_at: Instant::now(),
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.elapsed() >= self.policy.max_elapsed
+    }
+
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+
+    /// Sleeps for the next backoff delay and advances the attempt counter.
+    pub async fn wait(&mut self) {
+        let delay = self.policy.next_delay(self.attempt);
+        self.attempt += 1;
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Retries `f` under `policy` until it succeeds, its elapsed budget runs
+/// out, or `is_retryable` says the error is permanent.
+pub async fn retry_with_backoff<T, E, F, Fut>(
+    policy: BackoffPolicy,
+    is_retryable: impl Fn(&E) -> bool,
+    mut f: F,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut budget = BackoffBudget::new(policy);
+    loop {
+        match f().await {
+            Ok(v) => return Ok(v),
+            Err(e) if budget.is_exhausted() || !is_retryable(&e) => return Err(e),
+            Err(_) => budget.wait().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delay_bound_is_capped() {
+        let policy = BackoffPolicy::new(
+            Duration::from_millis(200),
+            2.0,
+            Duration::from_secs(5),
+            Duration::from_secs(30),
+        );
+
+        assert_eq!(policy.delay_bound(10), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn delay_bound_does_not_panic_for_large_attempts() {
+        let policy = BackoffPolicy::new(
+            Duration::from_millis(200),
+            2.0,
+            Duration::from_secs(5),
+            Duration::from_secs(30),
+        );
+
+        assert_eq!(policy.delay_bound(67), Duration::from_secs(5));
+        assert_eq!(policy.delay_bound(10_000), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn next_delay_never_exceeds_bound() {
+        let policy = BackoffPolicy::new(
+            Duration::from_millis(200),
+            2.0,
+            Duration::from_secs(5),
+            Duration::from_secs(30),
+        );
+
+        for attempt in 0..8 {
+            let bound = policy.delay_bound(attempt);
+            for _ in 0..20 {
+                assert!(policy.next_delay(attempt) <= bound);
+            }
+        }
+    }
+}
@@ -1,7 +1,82 @@
+use std::time::Duration;
 use chrono::{DateTime, Utc};
 use log::error;
-use reqwest::{Client, Url};
+use reqwest::{Client, Response, Url};
 use serde::{Deserialize, Serialize};
+use crate::backoff::{retry_with_backoff, BackoffPolicy};
+use crate::greeting_e2e::E2EError;
+
+/// Default retry policy for read calls: a handful of short, jittered
+/// retries so a single transient 5xx or dropped connection doesn't fail
+/// the whole run.
+const DEFAULT_RETRY_POLICY: BackoffPolicy = BackoffPolicy {
+    base: Duration::from_millis(200),
+    multiplier: 2.0,
+    cap: Duration::from_secs(5),
+    max_elapsed: Duration::from_secs(10),
+};
+
+/// Connection-level failures and retryable status codes are worth
+/// retrying; a 4xx `ApiError` means the request itself is wrong and
+/// retrying won't help.
+fn is_retryable(err: &E2EError) -> bool {
+    match err {
+        E2EError::ClientError(e) => {
+            e.is_connect() || e.is_timeout() || e.status().is_some_and(|s| s.is_server_error())
+        }
+        E2EError::ApiError { status, .. } => (500..600).contains(status),
+        _ => false,
+    }
+}
+
+/// The greeting API's error response shape: both fields are best-effort,
+/// since not every error path is guaranteed to populate them.
+#[derive(Deserialize, Debug)]
+struct ApiErrorBody {
+    message: Option<String>,
+    code: Option<String>,
+}
+
+/// Turns a non-2xx/204 response into a rich `E2EError::ApiError`, parsing
+/// the body as `ApiErrorBody` when possible and falling back to a
+/// status-only error otherwise.
+async fn to_api_error(response: Response) -> E2EError {
+    let status = response.status().as_u16();
+    let body = response.text().await.unwrap_or_default();
+
+    match serde_json::from_str::<ApiErrorBody>(&body) {
+        Ok(parsed) => E2EError::ApiError {
+            status,
+            message: parsed.message,
+            code: parsed.code,
+        },
+        Err(_) => {
+            error!("Unparseable error body (status {}): {}", status, body);
+            E2EError::ApiError {
+                status,
+                message: None,
+                code: None,
+            }
+        }
+    }
+}
+
+/// Which way a log page is read relative to `offset`. Only `Forward` is
+/// implemented: `fetch_all_log_entries`'s cursor only ever advances, so a
+/// `Backward` variant would need its own (untested) cursor logic. Add it
+/// back once a caller actually needs to page toward the start of the log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogDirection {
+    Forward,
+}
+
+impl LogDirection {
+    fn as_query_str(self) -> &'static str {
+        match self {
+            LogDirection::Forward => "forward",
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -10,6 +85,16 @@ pub struct LoggQuery {
     offset: i64,
     limit: i8,
 }
+
+impl LoggQuery {
+    pub fn new(direction: LogDirection, offset: i64, limit: i8) -> Self {
+        LoggQuery {
+            direction: direction.as_query_str().to_string(),
+            offset,
+            limit,
+        }
+    }
+}
 #[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq, Ord, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct GreetingLoggEntry {
@@ -22,74 +107,117 @@ pub struct GreetingLoggEntry {
 pub struct GreetingApiClient {
     client: Client,
     url: String,
+    retry_policy: BackoffPolicy,
 }
 
 impl GreetingApiClient {
-    pub async fn get_last_log_entry(&self) -> Result<Option<GreetingLoggEntry>, reqwest::Error> {
-        let response = self
-            .client
-            .get(format!("{}/log/last", &self.url))
-            .send()
-            .await?;
-
-        match response.status().as_str() {
-            "200" => Ok(Some(response.json::<GreetingLoggEntry>().await?)),
-            "204" => Ok(None),
-            _ => {
-                let status = response.error_for_status_ref().unwrap_err();
-                let error_message = response.text().await?;
-                error!("GreetingApiClient.get_last_log_entry: {}", error_message);
-                Err(status)
+    pub async fn get_last_log_entry(&self) -> Result<Option<GreetingLoggEntry>, E2EError> {
+        retry_with_backoff(self.retry_policy, is_retryable, || async {
+            let response = self
+                .client
+                .get(format!("{}/log/last", &self.url))
+                .send()
+                .await?;
+
+            match response.status().as_str() {
+                "200" => Ok(Some(response.json::<GreetingLoggEntry>().await?)),
+                "204" => Ok(None),
+                _ => Err(to_api_error(response).await),
             }
-        }
+        })
+        .await
     }
 
     pub async fn get_log_entries(
         &self,
         offset: i64,
         limit: u16,
-    ) -> Result<Vec<GreetingLoggEntry>, reqwest::Error> {
-        let response = self
-            .client
-            .get(format!("{}/log", &self.url))
-            .query(&[
-                ("direction", "forward"),
-                ("offset", &offset.to_string()),
-                ("limit", &limit.to_string()),
-            ])
-            .send()
-            .await?;
-
-        let status = response.status();
-
-        if status == 200 {
-            Ok(response.json::<Vec<GreetingLoggEntry>>().await?)
-        }else if status == 204 {
-            Ok(vec![])
-        } else {
-            let status = response.error_for_status_ref().unwrap_err();
-            let error_message = response.text().await?;
-            error!("GreetingApiClient.get_log_entries: {}", error_message);
-            Err(status)
+    ) -> Result<Vec<GreetingLoggEntry>, E2EError> {
+        self.get_log_page(&LoggQuery::new(LogDirection::Forward, offset, limit as i8))
+            .await
+    }
+
+    /// Same request as `get_log_entries`, but driven by a prebuilt
+    /// `LoggQuery`. Pages are read forward only, since `LogDirection` has
+    /// no other variant.
+    pub async fn get_log_page(&self, query: &LoggQuery) -> Result<Vec<GreetingLoggEntry>, E2EError> {
+        retry_with_backoff(self.retry_policy, is_retryable, || async {
+            let response = self
+                .client
+                .get(format!("{}/log", &self.url))
+                .query(&[
+                    ("direction", query.direction.as_str()),
+                    ("offset", &query.offset.to_string()),
+                    ("limit", &query.limit.to_string()),
+                ])
+                .send()
+                .await?;
+
+            let status = response.status();
+
+            if status == 200 {
+                Ok(response.json::<Vec<GreetingLoggEntry>>().await?)
+            } else if status == 204 {
+                Ok(vec![])
+            } else {
+                Err(to_api_error(response).await)
+            }
+        })
+        .await
+    }
+
+    /// Pages through the entire log in `direction` starting just past
+    /// `offset` (the id of the last entry already seen, or `0` for the
+    /// start of the log), following each page to the next until one comes
+    /// back empty. Used to replay the full log sequence rather than
+    /// trusting only the latest entries.
+    pub async fn fetch_all_log_entries(
+        &self,
+        direction: LogDirection,
+        offset: i64,
+        page_limit: i8,
+    ) -> Result<Vec<GreetingLoggEntry>, E2EError> {
+        let mut all_entries = Vec::new();
+        let mut cursor = offset;
+
+        loop {
+            let query = LoggQuery::new(direction, cursor + 1, page_limit);
+            let page = self.get_log_page(&query).await?;
+
+            let Some(last) = page.last() else {
+                break;
+            };
+            cursor = last.id;
+            all_entries.extend(page);
         }
+
+        Ok(all_entries)
     }
 
     pub fn new_client(url: String) -> Self {
+        Self::with_retry_policy(url, Duration::from_secs(30), DEFAULT_RETRY_POLICY)
+    }
+
+    /// Like `new_client`, but with a caller-supplied per-request timeout and
+    /// retry policy for read calls, driven by `E2ETestConfig`'s `api_*` fields.
+    pub fn with_retry_policy(url: String, timeout: Duration, retry_policy: BackoffPolicy) -> Self {
         Url::parse(&url).expect("Invalid url");
 
         GreetingApiClient {
             client: Client::builder()
-                .timeout(std::time::Duration::from_secs(30))
+                .timeout(timeout)
                 .build()
                 .expect("Failed to build client"),
             url,
+            retry_policy,
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::greeting_api::{GreetingApiClient};
+    use crate::greeting_api::{GreetingApiClient, LogDirection};
+    use crate::greeting_e2e::E2EError;
     use serde_json::json;
     use wiremock::matchers::{method, path, query_param};
     use wiremock::{Mock, MockServer, ResponseTemplate};
@@ -152,6 +280,61 @@ mod tests {
         assert!(resp.is_err())
     }
 
+    #[tokio::test]
+    async fn should_parse_structured_error_body_on_http_4xx() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/log/last"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(&json!({
+                "message": "offset out of range",
+                "code": "INVALID_OFFSET"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let greeting_api_client = GreetingApiClient::new_client(mock_server.uri());
+        let err = greeting_api_client
+            .get_last_log_entry()
+            .await
+            .expect_err("Expected an ApiError");
+
+        match err {
+            E2EError::ApiError { status, message, code } => {
+                assert_eq!(status, 400);
+                assert_eq!(message.as_deref(), Some("offset out of range"));
+                assert_eq!(code.as_deref(), Some("INVALID_OFFSET"));
+            }
+            other => panic!("Expected ApiError, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_fall_back_to_status_only_when_error_body_is_unparseable() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/log/last"))
+            .respond_with(ResponseTemplate::new(400).set_body_string("not json"))
+            .mount(&mock_server)
+            .await;
+
+        let greeting_api_client = GreetingApiClient::new_client(mock_server.uri());
+        let err = greeting_api_client
+            .get_last_log_entry()
+            .await
+            .expect_err("Expected an ApiError");
+
+        match err {
+            E2EError::ApiError { status, message, code } => {
+                assert_eq!(status, 400);
+                assert_eq!(message, None);
+                assert_eq!(code, None);
+            }
+            other => panic!("Expected ApiError, got {:?}", other),
+        }
+    }
+
     #[tokio::test]
     async fn should_get_latest_log_entries() {
         let expected_log_entries = json!([
@@ -177,6 +360,52 @@ mod tests {
 
         assert_eq!(json!(resp), expected_log_entries);
     }
+
+    #[tokio::test]
+    async fn should_fetch_all_log_entries_across_pages() {
+        let first_page = json!([
+            {"id": 1, "greetingId": 1, "messageId": "019b92bb-0088-77f1-8b09-5d56dfa72bc4", "created": "2026-01-01T20:00:00.414558Z"},
+            {"id": 2, "greetingId": 2, "messageId": "019b92bb-0088-77f1-8b09-5d56dfa72bc5", "created": "2026-01-01T21:00:00.414558Z"}
+        ]);
+        let second_page = json!([
+            {"id": 3, "greetingId": 3, "messageId": "019b92bb-0088-77f1-8b09-5d56dfa72bc6", "created": "2026-01-01T22:00:00.414558Z"}
+        ]);
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/log"))
+            .and(query_param("direction", "forward"))
+            .and(query_param("offset", "1"))
+            .and(query_param("limit", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&first_page))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/log"))
+            .and(query_param("direction", "forward"))
+            .and(query_param("offset", "3"))
+            .and(query_param("limit", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&second_page))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/log"))
+            .and(query_param("direction", "forward"))
+            .and(query_param("offset", "4"))
+            .and(query_param("limit", "2"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&mock_server)
+            .await;
+
+        let greeting_api_client = GreetingApiClient::new_client(mock_server.uri());
+        let resp = greeting_api_client
+            .fetch_all_log_entries(LogDirection::Forward, 0, 2)
+            .await
+            .expect("Expected all log entries");
+
+        assert_eq!(resp.len(), 3);
+        assert_eq!(resp.iter().map(|e| e.id).collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
 }
 // {
 //   "id": 1,
@@ -1,150 +1,366 @@
-use std::collections::HashMap;
-use std::time::Duration;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use confy::ConfyError;
 use log::error;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use tokio::time;
-use tokio::time::timeout;
 use tracing::{debug, info};
 use tracing::metadata::ParseLevelError;
+use schemars::JsonSchema;
 use uuid::Uuid;
+use validator::Validate;
 use crate::api::E2ETestConfig;
-use crate::greeting_api::GreetingApiClient;
-use crate::greeting_receiver::GreetingReceiverClient;
+use crate::backoff::{BackoffBudget, BackoffPolicy};
+use crate::greeting_api::{GreetingApiClient, GreetingLoggEntry, LogDirection};
+use crate::greeting_receiver::{GreetingCmd, GreetingReceiverClient, GreetingResponse};
+use crate::metrics::E2EMetrics;
+
+/// Lets a `Box<dyn MessageGenerator>` be downcast back to its concrete type,
+/// so a test can confirm `message_generators::build_generator` threaded a
+/// `GeneratorConfig`'s fields into the generator it built, not just that it
+/// built *some* generator. Blanket-implemented, so no `MessageGenerator`
+/// impl needs to do anything to support it.
+pub trait AsAny: std::any::Any {
+    fn as_any(&self) -> &dyn std::any::Any;
+}
+
+impl<T: std::any::Any> AsAny for T {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// A backend that produces the recipient/sender/content fields of a single
+/// greeting. Implementations range from deterministic fixtures to LLM-driven
+/// chaos data; see `crate::message_generators`.
+#[async_trait]
+pub trait MessageGenerator: Send + Sync + AsAny {
+    async fn generate_message(&self) -> Result<GeneratedMessage, E2EError>;
+}
+
+/// The length bounds mirror `GreetingTemplate`'s `validator` constraints, so
+/// a generator backend that ignores the requested format/schema still gets
+/// caught before its output is submitted as a greeting. Also derives
+/// `JsonSchema` so `message_generators::OllamaMessageGenerator` can pass
+/// the same bounds to Ollama's structured-output mode via `JsonStructure`.
+#[derive(Serialize, Deserialize, Validate, JsonSchema, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratedMessage {
+    pub to: String,
+    #[validate(length(min = 1, max = 20))]
+    #[schemars(length(min = 1, max = 20))]
+    pub from: String,
+    #[validate(length(min = 1, max = 50))]
+    #[schemars(length(min = 1, max = 50))]
+    pub heading: String,
+    #[validate(length(min = 1, max = 50))]
+    #[schemars(length(min = 1, max = 50))]
+    pub message: String,
+}
 
+/// A greeting whose delivery never succeeded, even after retries, along
+/// with the error from its last attempt. Distinguishing this from a task
+/// that simply hasn't been verified yet is what lets the run tell "the
+/// backend lost our message" apart from "we never managed to deliver it".
+#[derive(Debug, Clone)]
+pub struct SendFailure {
+    pub external_reference: String,
+    pub error: String,
+}
+
+#[derive(Debug)]
+pub struct E2ETestResult {
+    pub tasks: HashMap<String, TestTask>,
+    pub send_failures: Vec<SendFailure>,
+}
 
-pub async fn execute_e2e_test<F>(
+pub async fn execute_e2e_test(
     cfg: E2ETestConfig,
     api_client: GreetingApiClient,
     receiver_client: GreetingReceiverClient,
-    messsage_generator: F,
-) -> Result<HashMap<String, TestTask>, E2EError>
-where
-    F: Fn() -> GreetingCmd,
-{
-    let offset = match api_client.get_last_log_entry().await? {
+    message_generator: &dyn MessageGenerator,
+    metrics: &E2EMetrics,
+) -> Result<E2ETestResult, E2EError> {
+    let started = Instant::now();
+    let last_entry = api_client.get_last_log_entry().await?;
+    metrics.api_request_seconds.observe(started.elapsed().as_secs_f64());
+    let offset = match last_entry {
         Some(v) => v.id,
         None => 0,
     };
     info!("Log-entry offset-id: {}", offset);
 
-    let task_list = generate_test_tasks(cfg.num_iterations, messsage_generator);
+    let task_list = generate_test_tasks(cfg.num_iterations, message_generator, metrics).await;
     info!("Generated {} test tasks", &task_list.len());
 
-    let sent_test_tasks = send_messages(task_list, receiver_client).await;
-    info!("Sent {} test tasks", &sent_test_tasks.len());
+    let send_retry_policy = cfg.retry_sends.then(|| {
+        BackoffPolicy::new(
+            Duration::from_millis(cfg.api_retry_base_delay_ms),
+            2.0,
+            Duration::from_millis(cfg.api_retry_delay_cap_ms),
+            Duration::from_secs(cfg.api_retry_max_elapsed_secs),
+        )
+    });
+
+    let (sent_test_tasks, send_failures) =
+        send_messages(task_list, receiver_client, send_retry_policy, metrics).await;
+    info!(
+        "Sent {} test tasks, {} permanently failed",
+        &sent_test_tasks.len(),
+        &send_failures.len()
+    );
+    for failure in &send_failures {
+        error!(
+            "Gave up sending external_reference {}: {}",
+            failure.external_reference, failure.error
+        );
+    }
+
+    let verify_policy = BackoffPolicy::new(
+        Duration::from_millis(cfg.verify_base_delay_ms),
+        2.0,
+        Duration::from_millis(cfg.verify_delay_cap_ms),
+        Duration::from_secs(cfg.verify_max_elapsed_secs),
+    );
+
+    let tasks = verify_tasks(
+        &api_client,
+        offset,
+        cfg.greeting_log_limit,
+        verify_policy,
+        sent_test_tasks,
+        metrics,
+    )
+    .await?;
+
+    if cfg.full_log_verification {
+        verify_full_log_sequence(&api_client, offset, cfg.greeting_log_limit, &tasks, metrics).await?;
+    }
+
+    info!("{}", metrics.summary(cfg.num_iterations));
 
-    verify_tasks(api_client, offset, cfg.greeting_log_limit, sent_test_tasks).await
+    Ok(E2ETestResult {
+        tasks,
+        send_failures,
+    })
 }
 
-fn generate_test_tasks<F>(num_iterations: u16, messsage_generator: F) -> Vec<TestTask>
-where
-    F: Fn() -> GreetingCmd,
-{
-    (0..num_iterations)
-        .map(|_| messsage_generator())
-        .map(|m| TestTask {
-            external_reference: m.external_reference.to_string(),
-            message: m,
-            message_id: None,
-            greeting_logg_entry: None,
-        })
-        .fold(vec![], |mut acc, t| {
-            acc.push(t);
-            acc
-        })
+async fn generate_test_tasks(
+    num_iterations: u16,
+    message_generator: &dyn MessageGenerator,
+    metrics: &E2EMetrics,
+) -> Vec<TestTask> {
+    let mut tasks = Vec::with_capacity(num_iterations as usize);
+
+    for _ in 0..num_iterations {
+        let started = Instant::now();
+        let generated = message_generator.generate_message().await;
+        metrics.message_generation_seconds.observe(started.elapsed().as_secs_f64());
+
+        match generated {
+            Ok(m) => tasks.push(TestTask::from(m)),
+            Err(e) => error!("Failed generating message: {:?}", e),
+        }
+    }
+
+    tasks
 }
 
-pub fn generate_random_message() -> GreetingCmd {
-    GreetingCmd {
-        to: "arne".to_string(),
-        from: "arne".to_string(),
-        heading: "chrismas carg".to_string(),
-        message: "Happy christmas".to_string(),
-        external_reference: Uuid::now_v7().to_string(),
-        created: Utc::now(),
+impl From<GeneratedMessage> for TestTask {
+    fn from(m: GeneratedMessage) -> Self {
+        let external_reference = Uuid::now_v7().to_string();
+        TestTask {
+            external_reference: external_reference.clone(),
+            message: GreetingCmd {
+                external_reference,
+                to: m.to,
+                from: m.from,
+                heading: m.heading,
+                message: m.message,
+                created: Utc::now(),
+            },
+            message_id: None,
+            greeting_logg_entry: None,
+            sent_at: Instant::now(),
+        }
     }
 }
+
 async fn send_messages(
     task_list: Vec<TestTask>,
     greeting_receiver_client: GreetingReceiverClient,
-) -> HashMap<String, TestTask> {
+    send_retry_policy: Option<BackoffPolicy>,
+    metrics: &E2EMetrics,
+) -> (HashMap<String, TestTask>, Vec<SendFailure>) {
     let mut tasks = HashMap::new();
+    let mut send_failures = Vec::new();
 
     for task in task_list {
         debug!("Sending message: {:?}", &task.message.external_reference);
-        let resp = greeting_receiver_client.send(task.message.clone()).await;
+        // An `Err` here means delivery permanently failed: either sending
+        // isn't retried at all (the default, since submission isn't
+        // idempotent), or it was and the error was not retryable or the
+        // retry policy's budget ran out.
+        let started = Instant::now();
+        let resp = match send_retry_policy {
+            Some(policy) => greeting_receiver_client.send_with_retry(task.message.clone(), policy).await,
+            None => greeting_receiver_client.send(task.message.clone()).await,
+        };
+        metrics.receiver_request_seconds.observe(started.elapsed().as_secs_f64());
 
         match resp {
             Ok(v) => {
+                metrics.messages_sent.inc();
                 let mut performed_task = TestTask::from(task);
                 performed_task.message_id = Some(v.message_id.to_string());
+                performed_task.sent_at = Instant::now();
                 tasks.insert(v.message_id, performed_task);
             }
-            Err(e) => error!(
-                "Failed sending message.external_reference: {}, error: {:?}",
-                task.external_reference, e
-            ),
+            Err(e) => {
+                metrics.messages_send_failed.inc();
+                error!(
+                    "Failed sending message.external_reference: {}, error: {:?}",
+                    task.external_reference, e
+                );
+                send_failures.push(SendFailure {
+                    external_reference: task.external_reference,
+                    error: e.to_string(),
+                });
+            }
         }
     }
-    tasks
+    (tasks, send_failures)
 }
 
 async fn verify_tasks(
-    greeting_api_client: GreetingApiClient,
+    greeting_api_client: &GreetingApiClient,
     offset: i64,
     logg_limit: u16,
+    verify_policy: BackoffPolicy,
     mut tasks: HashMap<String, TestTask>,
+    metrics: &E2EMetrics,
 ) -> Result<HashMap<String, TestTask>, E2EError> {
-    const GREETING_API_RESPONSE_TIMEOUT_SECS: u64 = 10;
     let mut current_offset = offset;
+    let mut budget = BackoffBudget::new(verify_policy);
+
+    while tasks.iter().any(|e| e.1.greeting_logg_entry.is_none()) {
+        if budget.is_exhausted() {
+            let unverified = tasks
+                .iter()
+                .filter(|e| e.1.greeting_logg_entry.is_none())
+                .count();
+            metrics.tasks_timed_out.inc_by(unverified as u64);
+            return Err(E2EError::TimeoutError {
+                message: "Timeout waiting for new log entries".to_string(),
+                unverified,
+            });
+        }
 
-    let verified_tasks = timeout(
-        Duration::from_secs(GREETING_API_RESPONSE_TIMEOUT_SECS),
-        async {
-            while tasks.iter().any(|e| e.1.greeting_logg_entry.is_none()) {
-                let log_entries_result = greeting_api_client
-                    .get_log_entries(current_offset + 1, logg_limit)
-                    .await
-                    .map_err(|e| E2EError::ClientError(e));
-
-                let log_entries = match log_entries_result {
-                    Ok(v) => v,
-                    Err(e) => {
-                        panic!("Error when verifying tasks: {}", e)
-                    }
-                };
-
-                if log_entries.is_empty() {
-                    time::sleep(Duration::from_secs(1)).await;
-                    continue;
-                }
-
-                debug!(
-                    "Found {:?} entries from offset-id: {}",
-                    &log_entries.len(),
-                    current_offset
-                );
+        let started = Instant::now();
+        let log_entries = greeting_api_client
+            .get_log_entries(current_offset + 1, logg_limit)
+            .await?;
+        metrics.api_request_seconds.observe(started.elapsed().as_secs_f64());
 
-                for log_entry in log_entries {
-                    if let Some(entry) = tasks.get_mut(&log_entry.message_id) {
-                        entry.greeting_logg_entry = Some(log_entry.clone());
-                    }
+        if log_entries.is_empty() {
+            budget.wait().await;
+            continue;
+        }
 
-                    current_offset = log_entry.id;
-                }
+        debug!(
+            "Found {:?} entries from offset-id: {}",
+            &log_entries.len(),
+            current_offset
+        );
+
+        for log_entry in log_entries {
+            if let Some(entry) = tasks.get_mut(&log_entry.message_id) {
+                entry.greeting_logg_entry = Some(log_entry.clone());
+                metrics.tasks_verified.inc();
+                metrics
+                    .e2e_latency_seconds
+                    .observe(entry.sent_at.elapsed().as_secs_f64());
             }
-            Ok::<HashMap<String, TestTask>, E2EError>(tasks)
-        },
-    )
-        .await
-        .map_err(|_| E2EError::TimeoutError("Timeout waiting for new log entries".to_string()))??;
 
-    Ok(verified_tasks)
+            current_offset = log_entry.id;
+        }
+
+        // Progress was made: don't let a string of earlier empty polls
+        // keep inflating the delay for what is now a healthy stream.
+        budget.reset();
+    }
+
+    Ok(tasks)
 }
 
+/// Replays the full log forward from `offset` via `fetch_all_log_entries`
+/// and checks that every verified task's `message_id` actually appears, that
+/// entry ids never regress, and that the tasks' entries appear in the order
+/// the tasks were sent. `verify_tasks` only trusts whatever pages
+/// it happened to poll; this is a stronger, slower check for callers who
+/// want to rule out the backend silently dropping or reordering entries.
+async fn verify_full_log_sequence(
+    greeting_api_client: &GreetingApiClient,
+    offset: i64,
+    page_limit: u16,
+    tasks: &HashMap<String, TestTask>,
+    metrics: &E2EMetrics,
+) -> Result<(), E2EError> {
+    let page_limit = page_limit.min(i8::MAX as u16) as i8;
+    let started = Instant::now();
+    let log_entries = greeting_api_client
+        .fetch_all_log_entries(LogDirection::Forward, offset, page_limit)
+        .await?;
+    metrics.api_request_seconds.observe(started.elapsed().as_secs_f64());
+
+    let mut seen = HashSet::with_capacity(tasks.len());
+    let mut last_id = i64::MIN;
+    let mut last_sent_at: Option<Instant> = None;
+
+    for entry in &log_entries {
+        if entry.id < last_id {
+            return Err(E2EError::SequenceError {
+                message: format!(
+                    "log entry id {} arrived after {} in the full log replay",
+                    entry.id, last_id
+                ),
+            });
+        }
+        last_id = entry.id;
+
+        if let Some(task) = tasks.get(&entry.message_id) {
+            if last_sent_at.is_some_and(|prev| task.sent_at < prev) {
+                return Err(E2EError::SequenceError {
+                    message: format!(
+                        "message_id {} appears in the log ahead of a greeting sent before it",
+                        entry.message_id
+                    ),
+                });
+            }
+            last_sent_at = Some(task.sent_at);
+            seen.insert(entry.message_id.as_str());
+        }
+    }
+
+    let missing: Vec<&str> = tasks
+        .keys()
+        .map(String::as_str)
+        .filter(|message_id| !seen.contains(message_id))
+        .collect();
+
+    if !missing.is_empty() {
+        return Err(E2EError::SequenceError {
+            message: format!(
+                "message_id(s) missing from full log replay: {}",
+                missing.join(", ")
+            ),
+        });
+    }
+
+    Ok(())
+}
 
 #[derive(Debug, Clone)]
 pub(crate) struct TestTask {
@@ -152,39 +368,7 @@ pub(crate) struct TestTask {
     pub message: GreetingCmd,
     pub message_id: Option<String>,
     pub greeting_logg_entry: Option<GreetingLoggEntry>,
-}
-
-
-#[derive(Serialize, Deserialize, Clone, Debug)]
-#[serde(rename_all = "camelCase")]
-pub struct LoggQuery {
-    direction: String,
-    offset: i64,
-    limit: i8,
-}
-#[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq, Ord, Eq)]
-#[serde(rename_all = "camelCase")]
-pub struct GreetingLoggEntry {
-    pub(crate) id: i64,
-    pub(crate) greeting_id: i64,
-    pub(crate) message_id: String,
-    pub(crate) created: DateTime<Utc>,
-}
-
-#[derive(Serialize, Deserialize, Clone, Debug)]
-#[serde(rename_all = "camelCase")]
-pub struct GreetingCmd {
-    pub(crate) external_reference: String,
-    pub(crate) to: String,
-    pub(crate) from: String,
-    pub(crate) heading: String,
-    pub(crate) message: String,
-    pub(crate) created: DateTime<Utc>,
-}
-#[derive(Serialize, Deserialize, Debug, PartialOrd, PartialEq)]
-#[serde(rename_all = "camelCase")]
-pub struct GreetingResponse {
-    pub message_id: String,
+    pub sent_at: Instant,
 }
 
 
@@ -197,19 +381,32 @@ pub enum E2EError {
     LoggParseError(#[from] ParseLevelError),
     #[error("Client error: {0}")]
     ClientError(#[from] reqwest::Error),
-    #[error("Timeout error: {0}")]
-    TimeoutError(String),
+    #[error("Timeout error: {message} ({unverified} task(s) still unverified)")]
+    TimeoutError { message: String, unverified: usize },
+    #[error("Message generation error: {0}")]
+    GenerateMessageError(String),
+    #[error("Full log replay error: {message}")]
+    SequenceError { message: String },
+    #[error("API error (status {status}): {}", message.as_deref().unwrap_or("no message"))]
+    ApiError {
+        status: u16,
+        message: Option<String>,
+        code: Option<String>,
+    },
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::api::E2ETestConfig;
+    use crate::api::{E2ETestConfig, GeneratorConfig};
     use crate::greeting_api::GreetingApiClient;
     use crate::greeting_receiver::GreetingReceiverClient;
     use serde_json::json;
-    use wiremock::matchers::{body_json, method, path, query_param};
+    use wiremock::matchers::{body_partial_json, method, path, query_param};
     use wiremock::{Mock, MockServer, ResponseTemplate};
-    use crate::greeting_e2e::{execute_e2e_test, generate_random_message, GreetingCmd, GreetingResponse};
+    use crate::greeting_e2e::{execute_e2e_test, E2EError, GeneratedMessage, MessageGenerator};
+    use crate::greeting_receiver::GreetingResponse;
+    use crate::message_generators::LocalMessageGenerator;
+    use crate::metrics::E2EMetrics;
 
     #[tokio::test]
     async fn should_execute_e2e_for_0_task_successfully() {
@@ -227,6 +424,19 @@ mod tests {
             greeting_api_url: greeting_api_server.uri(),
             greeting_log_limit: 0,
             num_iterations: 0,
+            verify_base_delay_ms: 10,
+            verify_delay_cap_ms: 100,
+            verify_max_elapsed_secs: 1,
+            generator: GeneratorConfig::Random,
+            metrics_output_path: None,
+            full_log_verification: false,
+            readiness_poll_interval_ms: 10,
+            readiness_timeout_secs: 1,
+            api_request_timeout_secs: 5,
+            api_retry_base_delay_ms: 10,
+            api_retry_delay_cap_ms: 100,
+            api_retry_max_elapsed_secs: 1,
+            retry_sends: false,
         };
 
         let greeting_api_client =
@@ -238,12 +448,13 @@ mod tests {
             test_config,
             greeting_api_client,
             greeting_receiver_client,
-            generate_random_message,
+            &LocalMessageGenerator,
+            &E2EMetrics::new(),
         )
             .await;
 
         assert!(result.is_ok());
-        assert!(result.unwrap().is_empty())
+        assert!(result.unwrap().tasks.is_empty())
     }
 
     #[tokio::test]
@@ -269,20 +480,27 @@ mod tests {
             .mount(&greeting_api_server)
             .await;
 
-        let msg = json!({
-            "created": "2026-01-10T09:35:27.262Z",
-            "externalReference": "string",
+        struct TestMessageGenerator;
+
+        #[async_trait::async_trait]
+        impl MessageGenerator for TestMessageGenerator {
+            async fn generate_message(&self) -> Result<GeneratedMessage, E2EError> {
+                Ok(GeneratedMessage {
+                    to: "string".to_string(),
+                    from: "string".to_string(),
+                    heading: "string".to_string(),
+                    message: "string".to_string(),
+                })
+            }
+        }
+
+        let expected_body = json!({
             "from": "string",
             "heading": "string",
             "message": "string",
             "to": "string"
         });
 
-        let test_greeting_generator =
-            || serde_json::from_value::<GreetingCmd>(msg.clone()).expect("Could not parse json");
-
-        let greeting_msg = test_greeting_generator();
-
         let expected_response = GreetingResponse {
             message_id: "019b92bb-0088-77f1-8b09-5d56dfa72bc4".to_string(),
         };
@@ -290,7 +508,7 @@ mod tests {
 
         Mock::given(method("POST"))
             .and(path("/greeting"))
-            .and(body_json(greeting_msg))
+            .and(body_partial_json(expected_body))
             .respond_with(ResponseTemplate::new(200).set_body_json(&expected_response))
             .mount(&greeting_receiver_server)
             .await;
@@ -300,6 +518,19 @@ mod tests {
             greeting_api_url: greeting_api_server.uri(),
             greeting_log_limit: 10,
             num_iterations: 1,
+            verify_base_delay_ms: 10,
+            verify_delay_cap_ms: 100,
+            verify_max_elapsed_secs: 1,
+            generator: GeneratorConfig::Random,
+            metrics_output_path: None,
+            full_log_verification: false,
+            readiness_poll_interval_ms: 10,
+            readiness_timeout_secs: 1,
+            api_request_timeout_secs: 5,
+            api_retry_base_delay_ms: 10,
+            api_retry_delay_cap_ms: 100,
+            api_retry_max_elapsed_secs: 1,
+            retry_sends: false,
         };
 
         let greeting_api_client =
@@ -307,19 +538,126 @@ mod tests {
         let greeting_receiver_client =
             GreetingReceiverClient::new_client(test_config.greeting_receiver_url.to_string());
 
+        let metrics = E2EMetrics::new();
         let result = execute_e2e_test(
             test_config,
             greeting_api_client,
             greeting_receiver_client,
-            test_greeting_generator,
+            &TestMessageGenerator,
+            &metrics,
         )
             .await;
 
+        let result = result.unwrap();
         let num_verified = result
-            .unwrap()
+            .tasks
             .iter()
             .filter(|t| t.1.greeting_logg_entry.is_some())
             .count();
         assert_eq!(num_verified, 1);
+        assert!(result.send_failures.is_empty());
+        assert_eq!(metrics.tasks_verified.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn should_pass_full_log_verification_when_every_task_is_replayed() {
+        let greeting_api_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/log/last"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&greeting_api_server)
+            .await;
+
+        let log_entries = json!([
+            {"id": 1, "greetingId": 1, "messageId": "019b92bb-0088-77f1-8b09-5d56dfa72bc4", "created": "2026-01-01T20:00:00.414558Z"},
+        ]);
+
+        Mock::given(method("GET"))
+            .and(path("/log"))
+            .and(query_param("direction", "forward"))
+            .and(query_param("offset", "1"))
+            .and(query_param("limit", "10"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&log_entries))
+            .mount(&greeting_api_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/log"))
+            .and(query_param("direction", "forward"))
+            .and(query_param("offset", "2"))
+            .and(query_param("limit", "10"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&greeting_api_server)
+            .await;
+
+        struct TestMessageGenerator;
+
+        #[async_trait::async_trait]
+        impl MessageGenerator for TestMessageGenerator {
+            async fn generate_message(&self) -> Result<GeneratedMessage, E2EError> {
+                Ok(GeneratedMessage {
+                    to: "string".to_string(),
+                    from: "string".to_string(),
+                    heading: "string".to_string(),
+                    message: "string".to_string(),
+                })
+            }
+        }
+
+        let expected_body = json!({
+            "from": "string",
+            "heading": "string",
+            "message": "string",
+            "to": "string"
+        });
+
+        let expected_response = GreetingResponse {
+            message_id: "019b92bb-0088-77f1-8b09-5d56dfa72bc4".to_string(),
+        };
+        let greeting_receiver_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/greeting"))
+            .and(body_partial_json(expected_body))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&expected_response))
+            .mount(&greeting_receiver_server)
+            .await;
+
+        let test_config = E2ETestConfig {
+            greeting_receiver_url: greeting_receiver_server.uri(),
+            greeting_api_url: greeting_api_server.uri(),
+            greeting_log_limit: 10,
+            num_iterations: 1,
+            verify_base_delay_ms: 10,
+            verify_delay_cap_ms: 100,
+            verify_max_elapsed_secs: 1,
+            generator: GeneratorConfig::Random,
+            metrics_output_path: None,
+            full_log_verification: true,
+            readiness_poll_interval_ms: 10,
+            readiness_timeout_secs: 1,
+            api_request_timeout_secs: 5,
+            api_retry_base_delay_ms: 10,
+            api_retry_delay_cap_ms: 100,
+            api_retry_max_elapsed_secs: 1,
+            retry_sends: false,
+        };
+
+        let greeting_api_client =
+            GreetingApiClient::new_client(test_config.greeting_api_url.to_string());
+        let greeting_receiver_client =
+            GreetingReceiverClient::new_client(test_config.greeting_receiver_url.to_string());
+
+        let result = execute_e2e_test(
+            test_config,
+            greeting_api_client,
+            greeting_receiver_client,
+            &TestMessageGenerator,
+            &E2EMetrics::new(),
+        )
+            .await;
+
+        assert!(result.is_ok());
     }
 }
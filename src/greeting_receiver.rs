@@ -3,17 +3,23 @@ use log::error;
 use reqwest::header::{HeaderMap, CONTENT_TYPE};
 use reqwest::{Client, Error, Url};
 use serde::{Deserialize, Serialize};
-use uuid::Uuid;
+use crate::backoff::{retry_with_backoff, BackoffPolicy};
+
+fn is_retryable(err: &reqwest::Error) -> bool {
+    err.is_connect()
+        || err.is_timeout()
+        || err.status().is_some_and(|s| s.is_server_error())
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct GreetingCmd {
     pub(crate) external_reference: String,
-    to: String,
-    from: String,
-    heading: String,
-    message: String,
-    pub (crate) created: DateTime<Utc>,
+    pub(crate) to: String,
+    pub(crate) from: String,
+    pub(crate) heading: String,
+    pub(crate) message: String,
+    pub(crate) created: DateTime<Utc>,
 }
 #[derive(Serialize, Deserialize, Debug, PartialOrd, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -40,7 +46,25 @@ impl GreetingReceiverClient {
         }
     }
 
+    /// Submits `greeting` once, with no retry. Submission isn't idempotent,
+    /// so retrying a request that the server may already have accepted
+    /// risks a duplicate greeting; use `send_with_retry` to opt in anyway.
     pub async fn send(&self, greeting: GreetingCmd) -> Result<GreetingResponse, Error> {
+        self.send_once(&greeting).await
+    }
+
+    /// Like `send`, but retries with `policy` on connection failures and
+    /// retryable status codes. Only use this when the caller has decided
+    /// the risk of a duplicate greeting is acceptable.
+    pub async fn send_with_retry(
+        &self,
+        greeting: GreetingCmd,
+        policy: BackoffPolicy,
+    ) -> Result<GreetingResponse, Error> {
+        retry_with_backoff(policy, is_retryable, || self.send_once(&greeting)).await
+    }
+
+    async fn send_once(&self, greeting: &GreetingCmd) -> Result<GreetingResponse, Error> {
         let mut headers = HeaderMap::new();
         headers.insert(CONTENT_TYPE, "application/json".parse().unwrap());
 
@@ -48,7 +72,7 @@ impl GreetingReceiverClient {
             .client
             .post(format!("{}/greeting", &self.url))
             .headers(headers)
-            .json(&greeting)
+            .json(greeting)
             .send()
             .await?;
 
@@ -63,17 +87,6 @@ impl GreetingReceiverClient {
     }
 }
 
-pub fn generate_random_message() -> GreetingCmd {
-    GreetingCmd {
-        to: "arne".to_string(),
-        from: "arne".to_string(),
-        heading: "chrismas carg".to_string(),
-        message: "Happy christmas".to_string(),
-        external_reference: Uuid::now_v7().to_string(),
-        created: Utc::now(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use crate::greeting_receiver::{GreetingCmd, GreetingReceiverClient, GreetingResponse};
@@ -0,0 +1,124 @@
+//! Health-probe subsystem: waits for `/health/live` and `/health/ready` on
+//! each configured service before the e2e run starts, so a container that's
+//! still booting reads as "starting up" rather than a flaky run failure.
+use reqwest::Client;
+
+use crate::backoff::{BackoffBudget, BackoffPolicy};
+use crate::greeting_e2e::E2EError;
+
+/// Polls `{url}/health/live` and `{url}/health/ready` for every url in turn,
+/// waiting until each is ready or `policy`'s elapsed budget, shared across
+/// all urls, runs out.
+pub async fn wait_for_ready(urls: &[&str], policy: BackoffPolicy) -> Result<(), E2EError> {
+    let client = Client::new();
+    let mut budget = BackoffBudget::new(policy);
+
+    for url in urls {
+        budget.reset();
+
+        loop {
+            match probe(&client, url).await {
+                Readiness::Ready => break,
+                Readiness::Starting | Readiness::Down => {
+                    if budget.is_exhausted() {
+                        return Err(E2EError::TimeoutError {
+                            message: format!("{} never became ready", url),
+                            unverified: 0,
+                        });
+                    }
+                    budget.wait().await;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+enum Readiness {
+    Ready,
+    Starting,
+    Down,
+}
+
+/// A 200 on `/health/ready` means "go"; a 200 on `/health/live` without a
+/// ready 200 means the process is up but still warming up; anything else
+/// means the service isn't reachable at all.
+async fn probe(client: &Client, url: &str) -> Readiness {
+    let live = client
+        .get(format!("{}/health/live", url))
+        .send()
+        .await
+        .is_ok_and(|r| r.status().is_success());
+
+    if !live {
+        return Readiness::Down;
+    }
+
+    let ready = client
+        .get(format!("{}/health/ready", url))
+        .send()
+        .await
+        .is_ok_and(|r| r.status().is_success());
+
+    if ready {
+        Readiness::Ready
+    } else {
+        Readiness::Starting
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::backoff::BackoffPolicy;
+    use crate::health::wait_for_ready;
+
+    fn test_policy() -> BackoffPolicy {
+        BackoffPolicy::new(
+            Duration::from_millis(5),
+            2.0,
+            Duration::from_millis(50),
+            Duration::from_millis(500),
+        )
+    }
+
+    #[tokio::test]
+    async fn should_succeed_immediately_when_already_ready() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/health/live"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/health/ready"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let result = wait_for_ready(&[&server.uri()], test_policy()).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_time_out_when_service_never_reports_live() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/health/live"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let result = wait_for_ready(&[&server.uri()], test_policy()).await;
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,100 @@
+//! Brings up the real `greeting-receiver`/`greeting-api` stack (plus Kafka
+//! and Postgres) via the bundled `docker-compose.yml` and runs
+//! `execute_e2e_test` against it, instead of the `wiremock`-mocked unit
+//! tests. Only compiled in when the `integration` feature is enabled, so
+//! plain `cargo test` stays hermetic.
+use std::process::Command;
+use std::time::Duration;
+
+use log::info;
+
+use crate::api::E2ETestConfig;
+use crate::backoff::BackoffPolicy;
+use crate::greeting_api::GreetingApiClient;
+use crate::greeting_e2e::{execute_e2e_test, E2EError};
+use crate::greeting_receiver::GreetingReceiverClient;
+use crate::health::wait_for_ready;
+use crate::message_generators::build_generator;
+use crate::metrics::E2EMetrics;
+
+const COMPOSE_FILE: &str = "docker-compose.yml";
+const GREETING_RECEIVER_URL: &str = "http://localhost:8080";
+const GREETING_API_URL: &str = "http://localhost:8081";
+
+/// Runs `docker compose up -d`, waits for both services to answer their
+/// health endpoints, executes the e2e test against the live stack, then
+/// tears the stack down regardless of the run's outcome.
+pub async fn run(mut cfg: E2ETestConfig) -> Result<(), E2EError> {
+    cfg.greeting_receiver_url = GREETING_RECEIVER_URL.to_string();
+    cfg.greeting_api_url = GREETING_API_URL.to_string();
+
+    compose_up();
+    let result = run_against_live_stack(cfg).await;
+    compose_down();
+
+    result
+}
+
+async fn run_against_live_stack(cfg: E2ETestConfig) -> Result<(), E2EError> {
+    wait_for_ready(
+        &[&cfg.greeting_receiver_url, &cfg.greeting_api_url],
+        BackoffPolicy::new(
+            Duration::from_millis(cfg.readiness_poll_interval_ms),
+            2.0,
+            Duration::from_secs(5),
+            Duration::from_secs(cfg.readiness_timeout_secs),
+        ),
+    )
+    .await?;
+
+    let api_client = GreetingApiClient::with_retry_policy(
+        cfg.greeting_api_url.clone(),
+        Duration::from_secs(cfg.api_request_timeout_secs),
+        BackoffPolicy::new(
+            Duration::from_millis(cfg.api_retry_base_delay_ms),
+            2.0,
+            Duration::from_millis(cfg.api_retry_delay_cap_ms),
+            Duration::from_secs(cfg.api_retry_max_elapsed_secs),
+        ),
+    );
+    let receiver_client = GreetingReceiverClient::new_client(cfg.greeting_receiver_url.clone());
+    let generator = build_generator(&cfg.generator);
+    let metrics = E2EMetrics::new();
+    let metrics_output_path = cfg.metrics_output_path.clone();
+
+    let result = execute_e2e_test(cfg, api_client, receiver_client, generator.as_ref(), &metrics).await?;
+    info!(
+        "Integration run completed with {} task(s), {} send failure(s)",
+        result.tasks.len(),
+        result.send_failures.len(),
+    );
+
+    if let Some(path) = metrics_output_path {
+        metrics.write_to_file(&path).expect("Failed to write metrics file");
+    }
+
+    Ok(())
+}
+
+fn compose_up() {
+    run_compose(&["up", "-d"]);
+}
+
+fn compose_down() {
+    run_compose(&["down"]);
+}
+
+fn run_compose(args: &[&str]) {
+    let status = Command::new("docker")
+        .arg("compose")
+        .arg("-f")
+        .arg(COMPOSE_FILE)
+        .args(args)
+        .status();
+
+    match status {
+        Ok(s) if s.success() => {}
+        Ok(s) => log::error!("docker compose {:?} exited with {}", args, s),
+        Err(e) => log::error!("failed to run docker compose {:?}: {}", args, e),
+    }
+}
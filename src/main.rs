@@ -1,23 +1,82 @@
-use clap::Parser;
-use confy::ConfyError;
-use thiserror::Error;
-use crate::api::{load_e2e_config};
+use std::time::Duration;
+use clap::{Parser, ValueEnum};
+use log::info;
+use crate::api::load_e2e_config;
+use crate::backoff::BackoffPolicy;
+use crate::greeting_api::GreetingApiClient;
+use crate::greeting_e2e::{execute_e2e_test, E2EError};
+use crate::greeting_receiver::GreetingReceiverClient;
+use crate::health::wait_for_ready;
+use crate::message_generators::build_generator;
+use crate::metrics::E2EMetrics;
 
 mod api;
+mod backoff;
+mod greeting_api;
+mod greeting_e2e;
+mod greeting_receiver;
+mod health;
+#[cfg(feature = "integration")]
+mod integration;
+mod message_generators;
+mod metrics;
 
-fn main() -> Result<(), E2EError>{
+#[tokio::main]
+async fn main() -> Result<(), E2EError> {
     let args = CliArgs::parse();
     let cfg = load_e2e_config(&args.config_path)?;
-
     println!("E2E config: {:?}", cfg);
+
+    match args.mode {
+        RunMode::Unit => run_unit(cfg).await,
+        #[cfg(feature = "integration")]
+        RunMode::Integration => integration::run(cfg).await,
+        #[cfg(not(feature = "integration"))]
+        RunMode::Integration => panic!(
+            "--mode integration requires the crate to be built with `--features integration`"
+        ),
+    }
+}
+
+async fn run_unit(cfg: crate::api::E2ETestConfig) -> Result<(), E2EError> {
+    wait_for_ready(
+        &[&cfg.greeting_receiver_url, &cfg.greeting_api_url],
+        BackoffPolicy::new(
+            Duration::from_millis(cfg.readiness_poll_interval_ms),
+            2.0,
+            Duration::from_secs(5),
+            Duration::from_secs(cfg.readiness_timeout_secs),
+        ),
+    )
+    .await?;
+
+    let api_client = GreetingApiClient::with_retry_policy(
+        cfg.greeting_api_url.clone(),
+        Duration::from_secs(cfg.api_request_timeout_secs),
+        BackoffPolicy::new(
+            Duration::from_millis(cfg.api_retry_base_delay_ms),
+            2.0,
+            Duration::from_millis(cfg.api_retry_delay_cap_ms),
+            Duration::from_secs(cfg.api_retry_max_elapsed_secs),
+        ),
+    );
+    let receiver_client = GreetingReceiverClient::new_client(cfg.greeting_receiver_url.clone());
+    let generator = build_generator(&cfg.generator);
+    let metrics = E2EMetrics::new();
+    let metrics_output_path = cfg.metrics_output_path.clone();
+
+    let result = execute_e2e_test(cfg, api_client, receiver_client, generator.as_ref(), &metrics).await?;
+    info!(
+        "Completed e2e run with {} task(s), {} send failure(s)",
+        result.tasks.len(),
+        result.send_failures.len(),
+    );
+
+    if let Some(path) = metrics_output_path {
+        metrics.write_to_file(&path).expect("Failed to write metrics file");
+    }
+
     Ok(())
-//     load config and testspec
-//         number of messages
-//         number of clients
-//     get latest log entry
-//     generate greetings
-//     send greetings
-//     verify all greetings are stored and accessible via API checks
 }
 
 /// Runs e2e test for greeting-solution.
@@ -27,11 +86,16 @@ pub(crate) struct CliArgs {
     /// Path to configfile. If missing, a template file with default values is created.
     #[arg(short = 'c', long = "config")]
     pub config_path: String,
-}
 
+    /// `unit` runs against the service URLs in the config file; `integration`
+    /// brings up the real stack via docker-compose first (requires the
+    /// `integration` feature).
+    #[arg(long = "mode", value_enum, default_value_t = RunMode::Unit)]
+    pub mode: RunMode,
+}
 
-#[derive(Error, Debug)]
-enum E2EError{
-    #[error("E2E config error: {0}")]
-    ConfigError(#[from] ConfyError),
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RunMode {
+    Unit,
+    Integration,
 }
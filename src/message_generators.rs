@@ -1,11 +1,41 @@
+use async_trait::async_trait;
+use crate::api::GeneratorConfig;
 use crate::greeting_e2e::{E2EError, GeneratedMessage, MessageGenerator};
 use ollama_rs::generation::completion::request::GenerationRequest;
+use ollama_rs::generation::parameters::{FormatType, JsonStructure};
 use ollama_rs::Ollama;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Parses `raw` as a `GeneratedMessage` and checks it against the same
+/// length bounds `GreetingTemplate` enforces, so a model that emits
+/// well-formed but out-of-bounds JSON (e.g. ignoring the format directive)
+/// is caught here rather than surfacing as a rejected submission downstream.
+fn parse_and_validate(raw: &str) -> Result<GeneratedMessage, String> {
+    let message: GeneratedMessage = serde_json::from_str(raw).map_err(|e| e.to_string())?;
+    message.validate().map_err(|e| e.to_string())?;
+    Ok(message)
+}
+
+/// Picks the configured generator backend. Each arm owns construction of
+/// its generator, so adding a new backend only means adding an arm here
+/// and a new `MessageGenerator` impl, not touching the test-execution loop.
+pub fn build_generator(cfg: &GeneratorConfig) -> Box<dyn MessageGenerator> {
+    match cfg {
+        GeneratorConfig::Random => Box::new(LocalMessageGenerator),
+        GeneratorConfig::Ollama { model, host, prompt } => {
+            Box::new(OllamaMessageGenerator::new(model.clone(), host.clone(), prompt.clone()))
+        }
+        GeneratorConfig::OpenAiCompatible { base_url, api_key_env, model, prompt } => Box::new(
+            OpenAiCompatibleMessageGenerator::new(base_url.clone(), api_key_env.clone(), model.clone(), prompt.clone()),
+        ),
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LocalMessageGenerator;
 
+#[async_trait]
 impl MessageGenerator for LocalMessageGenerator {
     async fn generate_message(&self) -> Result<GeneratedMessage, E2EError> {
         Ok(GeneratedMessage {
@@ -17,14 +47,38 @@ impl MessageGenerator for LocalMessageGenerator {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct OllamaMessageGenerator;
+#[derive(Debug)]
+pub struct OllamaMessageGenerator {
+    ollama: Ollama,
+    model: String,
+    prompt: String,
+}
 
-impl MessageGenerator for OllamaMessageGenerator {
-    async fn generate_message(&self) -> Result<GeneratedMessage, E2EError> {
-        let ollama = Ollama::default();
-        let model = "tinyllama".to_string();
-        let prompt = "
+impl OllamaMessageGenerator {
+    pub fn new(model: String, host: String, prompt: String) -> Self {
+        OllamaMessageGenerator {
+            ollama: Ollama::try_new(host).expect("Invalid Ollama host"),
+            model,
+            prompt,
+        }
+    }
+
+    pub(crate) fn model(&self) -> &str {
+        &self.model
+    }
+}
+
+impl Default for OllamaMessageGenerator {
+    fn default() -> Self {
+        OllamaMessageGenerator::new(
+            "tinyllama".to_string(),
+            "http://localhost:11434".to_string(),
+            DEFAULT_GREETING_PROMPT.to_string(),
+        )
+    }
+}
+
+const DEFAULT_GREETING_PROMPT: &str = "
                 Write a JSON object with the following properties:
                  {'to': '', 'from': '','heading': '', 'message': ''}
                 The properties have these additional strict constraints:
@@ -40,51 +94,162 @@ impl MessageGenerator for OllamaMessageGenerator {
                 The response must be predictable.
              ";
 
-        let req = GenerationRequest::new(model, prompt);
+#[async_trait]
+impl MessageGenerator for OllamaMessageGenerator {
+    async fn generate_message(&self) -> Result<GeneratedMessage, E2EError> {
+        // `JsonStructure::new` derives the schema straight from
+        // `GeneratedMessage`'s `JsonSchema` impl, so the length bounds
+        // passed to Ollama always match the ones `parse_and_validate`
+        // checks the response against below.
+        let req = GenerationRequest::new(self.model.clone(), self.prompt.clone())
+            .format(FormatType::StructuredJson(JsonStructure::new::<GeneratedMessage>()));
 
-        let res = ollama.generate(req).await;
+        let res = self.ollama.generate(req).await;
 
-        let message_as_json = match res {
-            Ok(v) => parse_message(v.response),
+        let raw = match res {
+            Ok(v) => v.response,
             Err(e) => return Err(E2EError::GenerateMessageError(e.to_string())),
         };
 
-        Ok(serde_json::from_str::<GeneratedMessage>(&message_as_json)
-            .map_err(|e| E2EError::GenerateMessageError(e.to_string()))?)
+        // The format directive should already constrain the response to a
+        // single valid object; fall back to extracting the first balanced
+        // `{...}` span for backends that ignore the directive.
+        if let Ok(message) = parse_and_validate(&raw) {
+            return Ok(message);
+        }
+
+        let object = extract_balanced_object(&raw)
+            .ok_or_else(|| E2EError::GenerateMessageError(format!("no JSON object found in: {raw}")))?;
+
+        parse_and_validate(&object).map_err(E2EError::GenerateMessageError)
     }
 }
 
-fn parse_message(generated_message: String) -> String {
-    let mut json = false;
-    let mut json_map = vec![];
+/// Calls an OpenAI-compatible chat-completions endpoint and parses the
+/// first choice's message content as a `GeneratedMessage`. The API key is
+/// read from `api_key_env` at call time so secrets never land in config
+/// files.
+#[derive(Debug, Clone)]
+pub struct OpenAiCompatibleMessageGenerator {
+    client: reqwest::Client,
+    base_url: String,
+    api_key_env: String,
+    model: String,
+    prompt: String,
+}
 
-    for c in generated_message.lines() {
-        if c.trim().eq("{") {
-            json = true;
-        } else if c.trim().eq("}") {
-            json = false;
-            json_map.push(c);
+impl OpenAiCompatibleMessageGenerator {
+    pub fn new(base_url: String, api_key_env: String, model: String, prompt: String) -> Self {
+        OpenAiCompatibleMessageGenerator {
+            client: reqwest::Client::new(),
+            base_url,
+            api_key_env,
+            model,
+            prompt,
         }
-        if json {
-            json_map.push(c);
+    }
+
+    pub(crate) fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub(crate) fn model(&self) -> &str {
+        &self.model
+    }
+}
+
+#[async_trait]
+impl MessageGenerator for OpenAiCompatibleMessageGenerator {
+    async fn generate_message(&self) -> Result<GeneratedMessage, E2EError> {
+        let api_key = std::env::var(&self.api_key_env)
+            .map_err(|e| E2EError::GenerateMessageError(format!("{}: {}", self.api_key_env, e)))?;
+
+        let response = self
+            .client
+            .post(format!("{}/chat/completions", &self.base_url))
+            .bearer_auth(api_key)
+            .json(&serde_json::json!({
+                "model": self.model,
+                "response_format": { "type": "json_object" },
+                "messages": [{ "role": "user", "content": self.prompt }],
+            }))
+            .send()
+            .await
+            .map_err(|e| E2EError::GenerateMessageError(e.to_string()))?;
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| E2EError::GenerateMessageError(e.to_string()))?;
+
+        let content = body["choices"][0]["message"]["content"]
+            .as_str()
+            .ok_or_else(|| E2EError::GenerateMessageError("missing choices[0].message.content".to_string()))?;
+
+        parse_and_validate(content).map_err(E2EError::GenerateMessageError)
+    }
+}
+
+/// Extracts the first balanced `{...}` span from `text`, tolerating code
+/// fences, prose, or extra formatting around the JSON object.
+fn extract_balanced_object(text: &str) -> Option<String> {
+    let start = text.find('{')?;
+    let mut depth = 0;
+
+    for (i, c) in text[start..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(text[start..start + i + 1].to_string());
+                }
+            }
+            _ => {}
         }
     }
-    let m = json_map.concat();
-    m
+
+    None
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::greeting_e2e::MessageGenerator;
-    use crate::message_generators::OllamaMessageGenerator;
+    use crate::api::GeneratorConfig;
+    use crate::greeting_e2e::{AsAny, MessageGenerator};
+    use crate::message_generators::{
+        build_generator, extract_balanced_object, OllamaMessageGenerator, OpenAiCompatibleMessageGenerator,
+    };
     use futures::future::join_all;
 
+    const MESSAGE_COUNT: usize = 10;
+
+    #[test]
+    fn extract_balanced_object_ignores_surrounding_prose() {
+        let text = "Sure, here you go:\n```json\n{\"to\": \"a\", \"from\": \"b\"}\n```\nHope that helps!";
+
+        assert_eq!(
+            extract_balanced_object(text),
+            Some("{\"to\": \"a\", \"from\": \"b\"}".to_string())
+        );
+    }
+
+    #[test]
+    fn extract_balanced_object_handles_nested_braces() {
+        let text = "{\"a\": {\"b\": 1}}";
+
+        assert_eq!(extract_balanced_object(text), Some(text.to_string()));
+    }
+
+    #[test]
+    fn extract_balanced_object_returns_none_without_braces() {
+        assert_eq!(extract_balanced_object("no json here"), None);
+    }
+
     #[tokio::test]
     async fn should_generate_message() {
-        let MESSAGE_COUNT = 10;
-        let msg_generator = OllamaMessageGenerator {};
+        let msg_generator = OllamaMessageGenerator::default();
 
-        let awaiting_messages = (0..10)
+        let awaiting_messages = (0..MESSAGE_COUNT)
             .map(|_| msg_generator.generate_message())
             .collect::<Vec<_>>();
 
@@ -93,4 +258,50 @@ mod tests {
 
         assert_eq!(MESSAGE_COUNT, result_ok_count);
     }
+
+    #[tokio::test]
+    async fn should_build_local_generator_for_random_config() {
+        let generator = build_generator(&GeneratorConfig::Random);
+
+        let message = generator.generate_message().await;
+
+        assert!(message.is_ok());
+    }
+
+    #[test]
+    fn build_generator_threads_ollama_config_through() {
+        let generator = build_generator(&GeneratorConfig::Ollama {
+            model: "llama3".to_string(),
+            host: "http://ollama.internal:11434".to_string(),
+            prompt: "write a greeting".to_string(),
+        });
+
+        let ollama = generator
+            .as_ref()
+            .as_any()
+            .downcast_ref::<OllamaMessageGenerator>()
+            .expect("GeneratorConfig::Ollama should build an OllamaMessageGenerator");
+
+        assert_eq!(ollama.model(), "llama3");
+        assert_ne!(ollama.model(), OllamaMessageGenerator::default().model());
+    }
+
+    #[test]
+    fn build_generator_threads_open_ai_compatible_config_through() {
+        let generator = build_generator(&GeneratorConfig::OpenAiCompatible {
+            base_url: "http://vllm.internal:8000/v1".to_string(),
+            api_key_env: "TEST_OPENAI_API_KEY".to_string(),
+            model: "Llama-3-8B".to_string(),
+            prompt: "write a greeting".to_string(),
+        });
+
+        let open_ai = generator
+            .as_ref()
+            .as_any()
+            .downcast_ref::<OpenAiCompatibleMessageGenerator>()
+            .expect("GeneratorConfig::OpenAiCompatible should build an OpenAiCompatibleMessageGenerator");
+
+        assert_eq!(open_ai.base_url(), "http://vllm.internal:8000/v1");
+        assert_eq!(open_ai.model(), "Llama-3-8B");
+    }
 }
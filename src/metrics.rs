@@ -0,0 +1,299 @@
+use prometheus::core::Metric;
+use prometheus::{Encoder, Histogram, HistogramOpts, IntCounter, Registry, TextEncoder};
+use std::fmt;
+
+/// Buckets in seconds, covering "fast" verifications up to the slow tail
+/// this harness is meant to tolerate (cold-starting backends, etc.).
+const E2E_LATENCY_BUCKETS: &[f64] = &[0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 20.0, 30.0];
+
+/// Buckets in seconds for individual HTTP requests and LLM calls, which are
+/// expected to land well under the end-to-end verification latency above.
+const REQUEST_LATENCY_BUCKETS: &[f64] = &[0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// Prometheus counters and histograms for a single e2e run. Not meant to be
+/// shared across runs: construct one per `execute_e2e_test` call and either
+/// serve `render`'s output to a scraper or dump it to a text file with
+/// `write_to_file` afterwards.
+pub struct E2EMetrics {
+    registry: Registry,
+    pub messages_sent: IntCounter,
+    pub messages_send_failed: IntCounter,
+    pub tasks_verified: IntCounter,
+    pub tasks_timed_out: IntCounter,
+    pub e2e_latency_seconds: Histogram,
+    /// Latency of one client call to `greeting_receiver_url`, including any
+    /// retries it made.
+    pub receiver_request_seconds: Histogram,
+    /// Latency of one client call to `greeting_api_url`, including any
+    /// retries it made; a full log replay is recorded as a single call.
+    pub api_request_seconds: Histogram,
+    /// Latency of a single `MessageGenerator::generate_message` call.
+    pub message_generation_seconds: Histogram,
+}
+
+impl E2EMetrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let messages_sent =
+            IntCounter::new("e2e_messages_sent_total", "Greetings successfully submitted")
+                .expect("valid metric");
+        let messages_send_failed = IntCounter::new(
+            "e2e_messages_send_failed_total",
+            "Greetings that could not be submitted",
+        )
+        .expect("valid metric");
+        let tasks_verified = IntCounter::new(
+            "e2e_tasks_verified_total",
+            "Submitted greetings confirmed present in the log",
+        )
+        .expect("valid metric");
+        let tasks_timed_out = IntCounter::new(
+            "e2e_tasks_timed_out_total",
+            "Submitted greetings never confirmed before the verify budget ran out",
+        )
+        .expect("valid metric");
+        let e2e_latency_seconds = Histogram::with_opts(
+            HistogramOpts::new(
+                "e2e_latency_seconds",
+                "Time from sending a greeting to it being verified in the log",
+            )
+            .buckets(E2E_LATENCY_BUCKETS.to_vec()),
+        )
+        .expect("valid metric");
+        let receiver_request_seconds = Histogram::with_opts(
+            HistogramOpts::new(
+                "e2e_receiver_request_seconds",
+                "Latency of a single client call (with retries) to greeting_receiver_url",
+            )
+            .buckets(REQUEST_LATENCY_BUCKETS.to_vec()),
+        )
+        .expect("valid metric");
+        let api_request_seconds = Histogram::with_opts(
+            HistogramOpts::new(
+                "e2e_api_request_seconds",
+                "Latency of a single client call (with retries) to greeting_api_url",
+            )
+            .buckets(REQUEST_LATENCY_BUCKETS.to_vec()),
+        )
+        .expect("valid metric");
+        let message_generation_seconds = Histogram::with_opts(
+            HistogramOpts::new(
+                "e2e_message_generation_seconds",
+                "Latency of a single MessageGenerator::generate_message call",
+            )
+            .buckets(REQUEST_LATENCY_BUCKETS.to_vec()),
+        )
+        .expect("valid metric");
+
+        registry
+            .register(Box::new(messages_sent.clone()))
+            .expect("unique metric name");
+        registry
+            .register(Box::new(messages_send_failed.clone()))
+            .expect("unique metric name");
+        registry
+            .register(Box::new(tasks_verified.clone()))
+            .expect("unique metric name");
+        registry
+            .register(Box::new(tasks_timed_out.clone()))
+            .expect("unique metric name");
+        registry
+            .register(Box::new(e2e_latency_seconds.clone()))
+            .expect("unique metric name");
+        registry
+            .register(Box::new(receiver_request_seconds.clone()))
+            .expect("unique metric name");
+        registry
+            .register(Box::new(api_request_seconds.clone()))
+            .expect("unique metric name");
+        registry
+            .register(Box::new(message_generation_seconds.clone()))
+            .expect("unique metric name");
+
+        E2EMetrics {
+            registry,
+            messages_sent,
+            messages_send_failed,
+            tasks_verified,
+            tasks_timed_out,
+            e2e_latency_seconds,
+            receiver_request_seconds,
+            api_request_seconds,
+            message_generation_seconds,
+        }
+    }
+
+    /// Renders all registered metrics in the Prometheus text exposition
+    /// format, suitable for a scrape endpoint or a dumped `.prom` file.
+    pub fn render(&self) -> String {
+        let encoder = TextEncoder::new();
+        let metric_families = self.registry.gather();
+        let mut buffer = Vec::new();
+        encoder
+            .encode(&metric_families, &mut buffer)
+            .expect("metrics always encode");
+        String::from_utf8(buffer).expect("prometheus text format is UTF-8")
+    }
+
+    pub fn write_to_file(&self, path: &str) -> std::io::Result<()> {
+        std::fs::write(path, self.render())
+    }
+
+    /// Builds a human-readable end-of-run summary: verification success rate
+    /// (verified tasks out of `num_iterations`)
+    /// and p50/p99 for each latency histogram, so a user can tell how the
+    /// run went without parsing `render`'s raw text exposition.
+    pub fn summary(&self, num_iterations: u16) -> RunSummary {
+        RunSummary {
+            num_iterations,
+            messages_sent: self.messages_sent.get(),
+            messages_send_failed: self.messages_send_failed.get(),
+            tasks_verified: self.tasks_verified.get(),
+            tasks_timed_out: self.tasks_timed_out.get(),
+            e2e_latency: LatencySummary::from_histogram(&self.e2e_latency_seconds),
+            receiver_request_latency: LatencySummary::from_histogram(&self.receiver_request_seconds),
+            api_request_latency: LatencySummary::from_histogram(&self.api_request_seconds),
+            message_generation_latency: LatencySummary::from_histogram(&self.message_generation_seconds),
+        }
+    }
+}
+
+impl Default for E2EMetrics {
+    fn default() -> Self {
+        E2EMetrics::new()
+    }
+}
+
+/// p50/p99 estimated from a histogram's bucket boundaries by linear
+/// interpolation within the bucket containing the target quantile. This is
+/// the same approximation Prometheus's own `histogram_quantile` uses, and is
+/// only as precise as the configured bucket boundaries.
+#[derive(Debug, Clone, Copy)]
+pub struct LatencySummary {
+    pub count: u64,
+    pub p50_seconds: f64,
+    pub p99_seconds: f64,
+}
+
+impl LatencySummary {
+    fn from_histogram(histogram: &Histogram) -> Self {
+        let proto = histogram.metric().take_histogram();
+        let count = proto.get_sample_count();
+        LatencySummary {
+            count,
+            p50_seconds: quantile(&proto, 0.50),
+            p99_seconds: quantile(&proto, 0.99),
+        }
+    }
+}
+
+fn quantile(histogram: &prometheus::proto::Histogram, q: f64) -> f64 {
+    let count = histogram.get_sample_count();
+    if count == 0 {
+        return 0.0;
+    }
+    let target = q * count as f64;
+
+    let mut prev_bound = 0.0;
+    let mut prev_count = 0.0;
+    for bucket in histogram.get_bucket() {
+        let bound = bucket.get_upper_bound();
+        let cumulative = bucket.get_cumulative_count() as f64;
+        if target <= cumulative {
+            if cumulative == prev_count {
+                return bound;
+            }
+            let fraction = (target - prev_count) / (cumulative - prev_count);
+            return prev_bound + fraction * (bound - prev_bound);
+        }
+        prev_bound = bound;
+        prev_count = cumulative;
+    }
+    prev_bound
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RunSummary {
+    pub num_iterations: u16,
+    pub messages_sent: u64,
+    pub messages_send_failed: u64,
+    pub tasks_verified: u64,
+    pub tasks_timed_out: u64,
+    pub e2e_latency: LatencySummary,
+    pub receiver_request_latency: LatencySummary,
+    pub api_request_latency: LatencySummary,
+    pub message_generation_latency: LatencySummary,
+}
+
+impl fmt::Display for RunSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let success_rate = if self.num_iterations == 0 {
+            100.0
+        } else {
+            100.0 * self.tasks_verified as f64 / self.num_iterations as f64
+        };
+        writeln!(
+            f,
+            "e2e run summary: {}/{} verified ({:.1}% success), {} send failure(s), {} timed out",
+            self.tasks_verified, self.num_iterations, success_rate, self.messages_send_failed, self.tasks_timed_out
+        )?;
+        writeln!(
+            f,
+            "  e2e latency:           p50={:.3}s p99={:.3}s (n={})",
+            self.e2e_latency.p50_seconds, self.e2e_latency.p99_seconds, self.e2e_latency.count
+        )?;
+        writeln!(
+            f,
+            "  receiver request:      p50={:.3}s p99={:.3}s (n={})",
+            self.receiver_request_latency.p50_seconds, self.receiver_request_latency.p99_seconds, self.receiver_request_latency.count
+        )?;
+        writeln!(
+            f,
+            "  api request:           p50={:.3}s p99={:.3}s (n={})",
+            self.api_request_latency.p50_seconds, self.api_request_latency.p99_seconds, self.api_request_latency.count
+        )?;
+        write!(
+            f,
+            "  message generation:    p50={:.3}s p99={:.3}s (n={})",
+            self.message_generation_latency.p50_seconds, self.message_generation_latency.p99_seconds, self.message_generation_latency.count
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn histogram(buckets: &[f64]) -> Histogram {
+        Histogram::with_opts(HistogramOpts::new("test_histogram", "help").buckets(buckets.to_vec()))
+            .expect("valid metric")
+    }
+
+    #[test]
+    fn quantile_of_empty_histogram_is_zero() {
+        let proto = histogram(&[1.0, 2.0]).metric().take_histogram();
+
+        assert_eq!(quantile(&proto, 0.50), 0.0);
+        assert_eq!(quantile(&proto, 0.99), 0.0);
+    }
+
+    #[test]
+    fn quantile_of_single_sample_lands_in_its_bucket() {
+        let hist = histogram(&[1.0, 2.0, 5.0]);
+        hist.observe(0.5);
+        let proto = hist.metric().take_histogram();
+
+        assert_eq!(quantile(&proto, 0.50), 0.5);
+        assert_eq!(quantile(&proto, 0.99), 0.99);
+    }
+
+    #[test]
+    fn quantile_beyond_last_bucket_clamps_to_its_upper_bound() {
+        let hist = histogram(&[1.0, 2.0]);
+        hist.observe(100.0);
+        let proto = hist.metric().take_histogram();
+
+        assert_eq!(quantile(&proto, 0.99), 2.0);
+    }
+}